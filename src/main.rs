@@ -1,17 +1,31 @@
 extern crate piston_window;
 extern crate rand;
+#[macro_use]
+extern crate serde_derive;
+extern crate json5;
+extern crate serde;
+extern crate tinyfiledialogs;
 
 use piston_window::*;
 use piston_window::types::Color;
 use rand::Rng;
-use std::collections::LinkedList;
+use std::collections::{HashMap, HashSet, LinkedList, VecDeque};
+use std::fs;
+use std::path::PathBuf;
 
 const FOOD_COLOR: Color = [0.80, 0.00, 0.00, 1.0];
 const BORDER_COLOR: Color = [0.00, 0.00, 0.00, 1.0];
 const GAMEOVER_COLOR: Color = [0.90, 0.00, 0.00, 0.5];
+const SNAKE_ONE_COLOR: Color = [0.00, 0.80, 0.20, 1.0];
+const SNAKE_TWO_COLOR: Color = [0.20, 0.45, 0.90, 1.0];
+const TEXT_COLOR: Color = [1.00, 1.00, 1.00, 1.0];
 
-const MOVING_PERIOD: f64 = 0.1;
-const RESTART_TIME: f64 = 1.0;
+const TEXT_SIZE: u32 = 16;
+const HIGH_SCORE_FILE: &str = "highscore.txt";
+
+const BASE_MOVING_PERIOD: f64 = 0.2;
+const MIN_MOVING_PERIOD: f64 = 0.05;
+const MOVING_PERIOD_STEP: f64 = 0.005;
 
 const FOOD_SIZE: f64 = 10.0;
 const SNAKE_BLOCK_SIZE: f64 = 10.0;
@@ -19,27 +33,54 @@ const BORDER_WIDTH: f64 = 1.0;
 const BOARD_WIDTH: u32 = 50;
 const BOARD_HEIGHT: u32 = 50;
 
-fn hsv_to_rgb(h: f32, s: f32, v: f32) -> Color {
-    let h = h % 1.0;
-    let hi = (h * 6.0).floor() as i32;
-    let f = h * 6.0 - hi as f32;
-    let p = v * (1.0 - s);
-    let q = v * (1.0 - s * f);
-    let t = v * (1.0 - s * (1.0 - f));
-
-    match hi {
-        0 => [v, t, p, 1.0],
-        1 => [q, v, p, 1.0],
-        2 => [p, v, t, 1.0],
-        3 => [p, q, v, 1.0],
-        4 => [t, p, v, 1.0],
-        _ => [v, p, q, 1.0],
+// Runtime-tunable settings read from `snake.json5` at startup. Any missing
+// field falls back to its compile-time default in `Config::default`, so an
+// empty or absent file gives the built-in board size, speed and colors.
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+struct Config {
+    width: u32,
+    height: u32,
+    block_size: f64,
+    moving_period: f64,
+    food_color: Color,
+    border_color: Color,
+    snake_one_color: Color,
+    snake_two_color: Color,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            width: BOARD_WIDTH,
+            height: BOARD_HEIGHT,
+            block_size: SNAKE_BLOCK_SIZE,
+            moving_period: BASE_MOVING_PERIOD,
+            food_color: FOOD_COLOR,
+            border_color: BORDER_COLOR,
+            snake_one_color: SNAKE_ONE_COLOR,
+            snake_two_color: SNAKE_TWO_COLOR,
+        }
     }
 }
 
-fn get_rainbow_color(index: usize, time_offset: f64) -> Color {
-    let hue = ((index as f64 * 0.05 + time_offset * 0.3) % 1.0) as f32;
-    hsv_to_rgb(hue, 0.8, 0.8)
+impl Config {
+    // Look for `snake.json5` beside the executable; fall back to defaults if it
+    // is missing or fails to parse.
+    fn load() -> Config {
+        let path = match std::env::current_exe() {
+            Ok(mut p) => {
+                p.pop();
+                p.push("snake.json5");
+                p
+            }
+            Err(_) => PathBuf::from("snake.json5"),
+        };
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| json5::from_str(&s).ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -50,6 +91,14 @@ enum Direction {
     Right,
 }
 
+// The four moves the autopilot considers, in a fixed order for determinism.
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
 impl Direction {
     fn opposite(&self) -> Direction {
         match *self {
@@ -61,6 +110,14 @@ impl Direction {
     }
 }
 
+// Which corner a snake starts from in two-player mode. Each corner fixes both
+// the starting body layout and the initial direction the snake heads in.
+#[derive(Clone, Copy, PartialEq)]
+enum Corner {
+    UpperLeft,
+    LowerRight,
+}
+
 #[derive(Clone, PartialEq)]
 struct Block {
     x: i32,
@@ -74,11 +131,27 @@ struct Snake {
 }
 
 impl Snake {
-    fn new(x: i32, y: i32) -> Snake {
+    // Seed a four-block snake at the given corner, facing into the board.
+    fn new(corner: Corner, width: i32, height: i32) -> Snake {
         let mut body = LinkedList::new();
-        body.push_back(Block { x, y });
+        let direction = match corner {
+            Corner::UpperLeft => {
+                let y = 2;
+                for x in (1..5).rev() {
+                    body.push_back(Block { x, y });
+                }
+                Direction::Right
+            }
+            Corner::LowerRight => {
+                let y = height - 3;
+                for x in (width - 5)..(width - 1) {
+                    body.push_back(Block { x, y });
+                }
+                Direction::Left
+            }
+        };
         Snake {
-            direction: Direction::Right,
+            direction,
             body,
             tail: None,
         }
@@ -158,10 +231,24 @@ impl Snake {
         }
         return false;
     }
+
+    // Whether (x, y) hits any block of this snake's body. Used for cross-snake
+    // collisions, where the whole of the *other* snake is solid.
+    fn overlap_body(&self, x: i32, y: i32) -> bool {
+        for block in &self.body {
+            if x == block.x && y == block.y {
+                return true;
+            }
+        }
+        false
+    }
 }
 
 struct Game {
-    snake: Snake,
+    snake_one: Snake,
+    snake_two: Snake,
+    dir_one: Option<Direction>,
+    dir_two: Option<Direction>,
     food_exists: bool,
     food_x: i32,
     food_y: i32,
@@ -169,13 +256,31 @@ struct Game {
     height: i32,
     game_over: bool,
     waiting_time: f64,
-    total_time: f64, 
+    moving_period: f64,
+    base_period: f64,
+    block_size: f64,
+    food_color: Color,
+    border_color: Color,
+    snake_one_color: Color,
+    snake_two_color: Color,
+    score: u32,
+    highest: u32,
+    // When true, player one is steered by the pathfinding AI instead of WASD.
+    autopilot: bool,
+    // Set on the tick the round ends so the main loop can raise the game-over
+    // dialog exactly once, rather than every frame.
+    prompt_restart: bool,
 }
 
 impl Game {
-    fn new(width: i32, height: i32) -> Game {
+    fn new(config: &Config) -> Game {
+        let width = config.width as i32;
+        let height = config.height as i32;
         Game {
-            snake: Snake::new(2, 2),
+            snake_one: Snake::new(Corner::UpperLeft, width, height),
+            snake_two: Snake::new(Corner::LowerRight, width, height),
+            dir_one: None,
+            dir_two: None,
             food_exists: true,
             food_x: 6,
             food_y: 4,
@@ -183,50 +288,82 @@ impl Game {
             height,
             game_over: false,
             waiting_time: 0.0,
-            total_time: 0.0, 
+            moving_period: config.moving_period,
+            base_period: config.moving_period,
+            block_size: config.block_size,
+            food_color: config.food_color,
+            border_color: config.border_color,
+            snake_one_color: config.snake_one_color,
+            snake_two_color: config.snake_two_color,
+            score: 0,
+            highest: read_high_score(),
+            autopilot: false,
+            prompt_restart: false,
         }
     }
 
     fn key_pressed(&mut self, key: Key) {
+        // Toggle the autopilot regardless of round state (P for "pilot" — A is
+        // already player one's left in the WASD scheme).
+        if key == Key::P {
+            self.autopilot = !self.autopilot;
+            return;
+        }
+
         if self.game_over {
             return;
         }
 
-        let dir = match key {
+        // Player one steers with WASD, player two with the arrow keys.
+        let one = match key {
+            Key::W => Some(Direction::Up),
+            Key::S => Some(Direction::Down),
+            Key::A => Some(Direction::Left),
+            Key::D => Some(Direction::Right),
+            _ => None,
+        };
+        if let Some(d) = one {
+            if d != self.snake_one.head_direction().opposite() {
+                self.dir_one = Some(d);
+            }
+            return;
+        }
+
+        let two = match key {
             Key::Up => Some(Direction::Up),
             Key::Down => Some(Direction::Down),
             Key::Left => Some(Direction::Left),
             Key::Right => Some(Direction::Right),
             _ => None,
         };
-
-        if let Some(d) = dir {
-            if d == self.snake.head_direction().opposite() {
-                return;
+        if let Some(d) = two {
+            if d != self.snake_two.head_direction().opposite() {
+                self.dir_two = Some(d);
             }
         }
-
-        self.update_snake(dir);
     }
 
     fn restart(&mut self) {
-        self.snake = Snake::new(2, 2);
+        self.snake_one = Snake::new(Corner::UpperLeft, self.width, self.height);
+        self.snake_two = Snake::new(Corner::LowerRight, self.width, self.height);
+        self.dir_one = None;
+        self.dir_two = None;
         self.food_exists = true;
         self.food_x = 6;
         self.food_y = 4;
         self.game_over = false;
         self.waiting_time = 0.0;
-        self.total_time = 0.0; 
+        self.moving_period = self.base_period;
+        self.score = 0;
+        self.prompt_restart = false;
     }
 
     fn update(&mut self, delta_time: f64) {
         self.waiting_time += delta_time;
-        self.total_time += delta_time; 
 
         if self.game_over {
-            if self.waiting_time > RESTART_TIME {
-                self.restart();
-            }
+            // The main loop drives restart/quit through the dialog now; nothing
+            // advances while we wait for the player's choice.
             return;
         }
 
@@ -234,22 +371,54 @@ impl Game {
             self.add_food();
         }
 
-        if self.waiting_time > MOVING_PERIOD {
-            self.update_snake(None);
+        if self.waiting_time > self.moving_period {
+            if self.autopilot {
+                if let Some(dir) = self.ai_next_direction() {
+                    self.dir_one = Some(dir);
+                }
+            }
+            self.update_snakes();
         }
     }
 
     fn check_eating(&mut self) {
-        let (head_x, head_y) = self.snake.head_position();
-        if self.food_exists && self.food_x == head_x && self.food_y == head_y {
+        let (one_x, one_y) = self.snake_one.head_position();
+        let (two_x, two_y) = self.snake_two.head_position();
+        if self.food_exists && self.food_x == one_x && self.food_y == one_y {
+            self.food_exists = false;
+            self.snake_one.restore_tail();
+            self.add_score();
+        }
+        if self.food_exists && self.food_x == two_x && self.food_y == two_y {
             self.food_exists = false;
-            self.snake.restore_tail();
+            self.snake_two.restore_tail();
+            self.add_score();
         }
     }
 
-    fn check_if_snake_alive(&self, dir: Option<Direction>) -> bool {
-        let (next_x, next_y) = self.snake.next_head(dir);
-        if self.snake.overlap_tail(next_x, next_y) {
+    // Bump the round score and, when it beats the stored best, persist the new
+    // high so it survives a fresh launch of the process.
+    fn add_score(&mut self) {
+        self.score += 1;
+        if self.score > self.highest {
+            self.highest = self.score;
+            write_high_score(self.highest);
+        }
+
+        // Speed up as the snakes grow, but never past the playable floor.
+        let len = self.snake_one.body.len() + self.snake_two.body.len();
+        let period = self.base_period - (len as f64) * MOVING_PERIOD_STEP;
+        self.moving_period = period.max(MIN_MOVING_PERIOD).min(self.base_period);
+    }
+
+    // A snake lives if its next head stays inside the walls and clear of its own
+    // body and every block of the other snake.
+    fn check_if_snake_alive(&self, snake: &Snake, other: &Snake, dir: Option<Direction>) -> bool {
+        let (next_x, next_y) = snake.next_head(dir);
+        if snake.overlap_tail(next_x, next_y) {
+            return false;
+        }
+        if other.overlap_body(next_x, next_y) {
             return false;
         }
         next_x > 0 && next_y > 0 && next_x < self.width - 1 && next_y < self.height - 1
@@ -259,7 +428,7 @@ impl Game {
         let mut rng = rand::thread_rng();
         let mut new_x = rng.gen_range(1..(self.width - 1));
         let mut new_y = rng.gen_range(1..(self.height - 1));
-        while self.snake.overlap_tail(new_x, new_y) {
+        while self.snake_one.overlap_tail(new_x, new_y) || self.snake_two.overlap_tail(new_x, new_y) {
             new_x = rng.gen_range(1..(self.width - 1));
             new_y = rng.gen_range(1..(self.height - 1));
         }
@@ -268,61 +437,179 @@ impl Game {
         self.food_exists = true;
     }
 
-    fn update_snake(&mut self, dir: Option<Direction>) {
-        if self.check_if_snake_alive(dir) {
-            self.snake.move_forward(dir);
+    // Advance both snakes one tick. Whoever's next head is blocked dies; if only
+    // one dies the other wins, if both die it's a draw — either way the round ends.
+    fn update_snakes(&mut self) {
+        // Autopilot is a single-snake showcase: player two is frozen in its
+        // corner (a static obstacle the BFS avoids) so the round lasts as long
+        // as the AI keeps snake one alive.
+        if self.autopilot {
+            if self.check_if_snake_alive(&self.snake_one, &self.snake_two, self.dir_one) {
+                self.snake_one.move_forward(self.dir_one);
+                self.check_eating();
+            } else {
+                self.game_over = true;
+                self.prompt_restart = true;
+            }
+            self.waiting_time = 0.0;
+            return;
+        }
+
+        let one_alive = self.check_if_snake_alive(&self.snake_one, &self.snake_two, self.dir_one);
+        let two_alive = self.check_if_snake_alive(&self.snake_two, &self.snake_one, self.dir_two);
+
+        if one_alive && two_alive {
+            self.snake_one.move_forward(self.dir_one);
+            self.snake_two.move_forward(self.dir_two);
             self.check_eating();
         } else {
             self.game_over = true;
+            self.prompt_restart = true;
         }
         self.waiting_time = 0.0;
     }
 
-    fn draw(&self, con: &Context, g: &mut G2d) {
-        let mut i = 0;
-        for block in &self.snake.body {
-            let color = get_rainbow_color(i, self.total_time);
-            draw_block(color, block.x, block.y, con, g);
-            i += 1;
+    // A cell the snake cannot occupy: a wall or any block of either snake.
+    fn cell_blocked(&self, x: i32, y: i32) -> bool {
+        if x <= 0 || y <= 0 || x >= self.width - 1 || y >= self.height - 1 {
+            return true;
+        }
+        self.snake_one.overlap_body(x, y) || self.snake_two.overlap_body(x, y)
+    }
+
+    // Choose player one's next move: the first step of the shortest path to the
+    // food, or — when the food is unreachable — the move that leaves the most
+    // free space reachable so the snake avoids boxing itself in.
+    fn ai_next_direction(&self) -> Option<Direction> {
+        let head = self.snake_one.head_position();
+        let goal = (self.food_x, self.food_y);
+
+        self.bfs_first_step(head, goal)
+            .or_else(|| self.safest_direction(head))
+    }
+
+    // Breadth-first search from the head to the food over free cells, returning
+    // the direction of the first step along the shortest path.
+    fn bfs_first_step(&self, start: (i32, i32), goal: (i32, i32)) -> Option<Direction> {
+        if start == goal {
+            return None;
+        }
+
+        let mut came: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut visited: HashSet<(i32, i32)> = HashSet::new();
+        let mut queue: VecDeque<(i32, i32)> = VecDeque::new();
+        queue.push_back(start);
+        visited.insert(start);
+
+        while let Some(cur) = queue.pop_front() {
+            if cur == goal {
+                // Walk the predecessors back until the cell adjacent to the head.
+                let mut node = cur;
+                while came[&node] != start {
+                    node = came[&node];
+                }
+                return direction_between(start, node);
+            }
+            for &dir in DIRECTIONS.iter() {
+                let next = move_cell(cur, dir);
+                if visited.contains(&next) || self.cell_blocked(next.0, next.1) {
+                    continue;
+                }
+                visited.insert(next);
+                came.insert(next, cur);
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+
+    // Of the legal moves from the head, the one whose resulting cell can reach
+    // the largest region of free space (flood fill).
+    fn safest_direction(&self, head: (i32, i32)) -> Option<Direction> {
+        let mut best: Option<(Direction, usize)> = None;
+        for &dir in DIRECTIONS.iter() {
+            let next = move_cell(head, dir);
+            if self.cell_blocked(next.0, next.1) {
+                continue;
+            }
+            let space = self.reachable_space(next);
+            match best {
+                Some((_, b)) if b >= space => {}
+                _ => best = Some((dir, space)),
+            }
+        }
+        best.map(|(dir, _)| dir)
+    }
+
+    // Count of free cells reachable from `start` via flood fill.
+    fn reachable_space(&self, start: (i32, i32)) -> usize {
+        let mut visited: HashSet<(i32, i32)> = HashSet::new();
+        let mut queue: VecDeque<(i32, i32)> = VecDeque::new();
+        queue.push_back(start);
+        visited.insert(start);
+        while let Some(cur) = queue.pop_front() {
+            for &dir in DIRECTIONS.iter() {
+                let next = move_cell(cur, dir);
+                if visited.contains(&next) || self.cell_blocked(next.0, next.1) {
+                    continue;
+                }
+                visited.insert(next);
+                queue.push_back(next);
+            }
+        }
+        visited.len()
+    }
+
+    fn draw(&self, con: &Context, g: &mut G2d, glyphs: Option<&mut Glyphs>) {
+        let bs = self.block_size;
+        for block in &self.snake_one.body {
+            draw_block(self.snake_one_color, block.x, block.y, bs, con, g);
+        }
+        for block in &self.snake_two.body {
+            draw_block(self.snake_two_color, block.x, block.y, bs, con, g);
         }
 
         if self.food_exists {
-            draw_block(FOOD_COLOR, self.food_x, self.food_y, con, g);
+            draw_block(self.food_color, self.food_x, self.food_y, bs, con, g);
         }
 
         draw_rectangle(
-            BORDER_COLOR,
+            self.border_color,
             0,
             0,
             self.width,
             1,
+            bs,
             con,
             g,
         );
         draw_rectangle(
-            BORDER_COLOR,
+            self.border_color,
             0,
             self.height - 1,
             self.width,
             1,
+            bs,
             con,
             g,
         );
         draw_rectangle(
-            BORDER_COLOR,
+            self.border_color,
             0,
             0,
             1,
             self.height,
+            bs,
             con,
             g,
         );
         draw_rectangle(
-            BORDER_COLOR,
+            self.border_color,
             self.width - 1,
             0,
             1,
             self.height,
+            bs,
             con,
             g,
         );
@@ -334,35 +621,109 @@ impl Game {
                 0,
                 self.width,
                 self.height,
+                bs,
                 con,
                 g,
             );
         }
+
+        // Only render the status line if a font was available at startup.
+        if let Some(glyphs) = glyphs {
+            let length = self.snake_one.body.len() + self.snake_two.body.len();
+            let status = format!(
+                "Score: {}   High: {}   Length: {}",
+                self.score, self.highest, length
+            );
+            text::Text::new_color(TEXT_COLOR, TEXT_SIZE)
+                .draw(
+                    &status,
+                    glyphs,
+                    &con.draw_state,
+                    con.transform.trans(bs, bs * 2.0),
+                    g,
+                )
+                .ok();
+        }
     }
 }
 
-fn draw_block(color: Color, x: i32, y: i32, con: &Context, g: &mut G2d) {
-    let gui_x = (x as f64) * SNAKE_BLOCK_SIZE;
-    let gui_y = (y as f64) * SNAKE_BLOCK_SIZE;
+// The high score lives in a small text file next to the executable so it
+// survives process restarts.
+fn high_score_path() -> PathBuf {
+    match std::env::current_exe() {
+        Ok(mut path) => {
+            path.pop();
+            path.push(HIGH_SCORE_FILE);
+            path
+        }
+        Err(_) => PathBuf::from(HIGH_SCORE_FILE),
+    }
+}
+
+fn read_high_score() -> u32 {
+    fs::read_to_string(high_score_path())
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn write_high_score(value: u32) {
+    let _ = fs::write(high_score_path(), value.to_string());
+}
+
+// The grid cell reached by stepping one square in `dir` from `pos`.
+fn move_cell(pos: (i32, i32), dir: Direction) -> (i32, i32) {
+    let (x, y) = pos;
+    match dir {
+        Direction::Up => (x, y - 1),
+        Direction::Down => (x, y + 1),
+        Direction::Left => (x - 1, y),
+        Direction::Right => (x + 1, y),
+    }
+}
+
+// The direction from `from` to an orthogonally adjacent cell `to`.
+fn direction_between(from: (i32, i32), to: (i32, i32)) -> Option<Direction> {
+    match (to.0 - from.0, to.1 - from.1) {
+        (0, -1) => Some(Direction::Up),
+        (0, 1) => Some(Direction::Down),
+        (-1, 0) => Some(Direction::Left),
+        (1, 0) => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+fn draw_block(color: Color, x: i32, y: i32, block_size: f64, con: &Context, g: &mut G2d) {
+    let gui_x = (x as f64) * block_size;
+    let gui_y = (y as f64) * block_size;
 
     rectangle(
         color,
         [
             gui_x,
             gui_y,
-            SNAKE_BLOCK_SIZE,
-            SNAKE_BLOCK_SIZE,
+            block_size,
+            block_size,
         ],
         con.transform,
         g,
     );
 }
 
-fn draw_rectangle(color: Color, x: i32, y: i32, width: i32, height: i32, con: &Context, g: &mut G2d) {
-    let x = (x as f64) * SNAKE_BLOCK_SIZE;
-    let y = (y as f64) * SNAKE_BLOCK_SIZE;
-    let width = (width as f64) * SNAKE_BLOCK_SIZE;
-    let height = (height as f64) * SNAKE_BLOCK_SIZE;
+fn draw_rectangle(
+    color: Color,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    block_size: f64,
+    con: &Context,
+    g: &mut G2d,
+) {
+    let x = (x as f64) * block_size;
+    let y = (y as f64) * block_size;
+    let width = (width as f64) * block_size;
+    let height = (height as f64) * block_size;
 
     rectangle(
         color,
@@ -373,32 +734,55 @@ fn draw_rectangle(color: Color, x: i32, y: i32, width: i32, height: i32, con: &C
 }
 
 fn main() {
-    let (width, height) = (BOARD_WIDTH, BOARD_HEIGHT);
+    let config = Config::load();
     let mut window: PistonWindow = WindowSettings::new(
         "Snake Game",
         [
-            (width as f64) * SNAKE_BLOCK_SIZE,
-            (height as f64) * SNAKE_BLOCK_SIZE,
+            (config.width as f64) * config.block_size,
+            (config.height as f64) * config.block_size,
         ],
     )
     .exit_on_esc(true)
     .build()
     .unwrap();
 
-    let mut game = Game::new(width as i32, height as i32);
-    
+    let mut game = Game::new(&config);
+
+    // Text is optional: if the font is missing we still run, just without the
+    // on-screen score readout, rather than panicking at startup.
+    let mut glyphs = window.load_font("assets/FiraSans-Regular.ttf").ok();
+
     while let Some(event) = window.next() {
         if let Some(Button::Keyboard(key)) = event.press_args() {
             game.key_pressed(key);
         }
-        
-        window.draw_2d(&event, |c, g, _| {
+
+        window.draw_2d(&event, |c, g, device| {
             clear([0.5, 0.5, 0.5, 1.0], g);
-            game.draw(&c, g);
+            game.draw(&c, g, glyphs.as_mut());
+            if let Some(glyphs) = glyphs.as_mut() {
+                glyphs.factory.encoder.flush(device);
+            }
         });
-        
+
         event.update(|args| {
             game.update(args.dt);
         });
+
+        // On the single frame the round ends, ask the player what to do next.
+        // Kept off the render/update path so it fires exactly once per edge.
+        if game.prompt_restart {
+            game.prompt_restart = false;
+            let message = format!("Game over!\nScore: {}   High: {}", game.score, game.highest);
+            match tinyfiledialogs::message_box_yes_no(
+                "Snake Game",
+                &message,
+                tinyfiledialogs::MessageBoxIcon::Question,
+                tinyfiledialogs::YesNoChoice::Yes,
+            ) {
+                tinyfiledialogs::YesNoChoice::Yes => game.restart(),
+                tinyfiledialogs::YesNoChoice::No => break,
+            }
+        }
     }
 }